@@ -1,24 +1,54 @@
 use std::f32;
+use std::fmt;
 use std::marker::PhantomData;
 use std::os::raw::{c_char, c_void};
+use std::ptr;
 
 use crate::sys;
 use crate::window::WindowFlags;
 use crate::{Id, Ui};
 
+/// Boxed callback invoked while a [`ChildWindow`]'s size constraints are being resolved.
+type SizeCallback<'a> = Box<dyn FnMut(&mut SizeCallbackData) + 'a>;
+
 /// Builder for a child window
-#[derive(Copy, Clone, Debug)]
+///
+/// Note: as of the `size_constraints_callback` addition, this type can hold a boxed closure and
+/// is no longer `Copy`/`Clone` (it was both previously). Build a fresh `ChildWindow` per use
+/// instead of stashing one to reuse.
 #[must_use]
 pub struct ChildWindow<'a> {
     id: Id<'a>,
     flags: WindowFlags,
     size: [f32; 2],
     content_size: [f32; 2],
+    size_constraints: Option<([f32; 2], [f32; 2])>,
+    size_callback: Option<SizeCallback<'a>>,
+    scroll_x_ratio: Option<f32>,
+    scroll_y_ratio: Option<f32>,
     focused: bool,
     bg_alpha: f32,
     border: bool,
 }
 
+impl<'a> fmt::Debug for ChildWindow<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChildWindow")
+            .field("id", &self.id)
+            .field("flags", &self.flags)
+            .field("size", &self.size)
+            .field("content_size", &self.content_size)
+            .field("size_constraints", &self.size_constraints)
+            .field("size_callback", &self.size_callback.is_some())
+            .field("scroll_x_ratio", &self.scroll_x_ratio)
+            .field("scroll_y_ratio", &self.scroll_y_ratio)
+            .field("focused", &self.focused)
+            .field("bg_alpha", &self.bg_alpha)
+            .field("border", &self.border)
+            .finish()
+    }
+}
+
 impl<'a> ChildWindow<'a> {
     /// Creates a new child window builder with the given ID
     pub fn new<T: Into<Id<'a>>>(id: T) -> ChildWindow<'a> {
@@ -27,6 +57,10 @@ impl<'a> ChildWindow<'a> {
             flags: WindowFlags::empty(),
             size: [0.0, 0.0],
             content_size: [0.0, 0.0],
+            size_constraints: None,
+            size_callback: None,
+            scroll_x_ratio: None,
+            scroll_y_ratio: None,
             focused: false,
             bg_alpha: f32::NAN,
             border: false,
@@ -59,6 +93,34 @@ impl<'a> ChildWindow<'a> {
         self.content_size = size;
         self
     }
+    /// Sets the child window size constraints, applied when this builder's `begin()` is called.
+    ///
+    /// Use `-1.0` on either axis of `size_min`/`size_max` to preserve the window's current size
+    /// on that axis.
+    #[inline]
+    pub fn size_constraints(mut self, size_min: [f32; 2], size_max: [f32; 2]) -> Self {
+        self.size_constraints = Some((size_min, size_max));
+        self
+    }
+    /// Sets the child window size constraints, applied when this builder's `begin()` is called,
+    /// and a callback that can
+    /// further adjust the requested size (e.g. to snap to a grid or enforce an aspect ratio).
+    ///
+    /// The callback is only invoked while the returned builder is used to `begin()` the window.
+    #[inline]
+    pub fn size_constraints_callback<F>(
+        mut self,
+        size_min: [f32; 2],
+        size_max: [f32; 2],
+        callback: F,
+    ) -> Self
+    where
+        F: FnMut(&mut SizeCallbackData) + 'a,
+    {
+        self.size_constraints = Some((size_min, size_max));
+        self.size_callback = Some(Box::new(callback));
+        self
+    }
     /// Sets the window focused state, which can be used to bring the window to front
     #[inline]
     pub fn focused(mut self, focused: bool) -> Self {
@@ -90,6 +152,24 @@ impl<'a> ChildWindow<'a> {
         self.flags.set(WindowFlags::NO_SCROLLBAR, !value);
         self
     }
+    /// Sets the horizontal scroll position for this frame, as a ratio `[0.0, 1.0]` of the
+    /// maximum scroll position.
+    ///
+    /// Useful to pin the view to an edge every frame, e.g. `1.0` to follow newly appended content.
+    #[inline]
+    pub fn scroll_x_ratio(mut self, ratio: f32) -> Self {
+        self.scroll_x_ratio = Some(ratio);
+        self
+    }
+    /// Sets the vertical scroll position for this frame, as a ratio `[0.0, 1.0]` of the
+    /// maximum scroll position.
+    ///
+    /// Useful to pin the view to an edge every frame, e.g. `1.0` to follow newly appended content.
+    #[inline]
+    pub fn scroll_y_ratio(mut self, ratio: f32) -> Self {
+        self.scroll_y_ratio = Some(ratio);
+        self
+    }
     /// Enables/disables vertical scrolling with the mouse wheel.
     ///
     /// Enabled by default.
@@ -228,11 +308,47 @@ impl<'a> ChildWindow<'a> {
         self.flags |= WindowFlags::NO_INPUTS;
         self
     }
+    /// Enables/disables flattening gamepad/keyboard navigation across this window's border, so it
+    /// is treated as part of the same navigable surface as its parent or siblings.
+    ///
+    /// Disabled by default.
+    #[inline]
+    pub fn nav_flattened(mut self, value: bool) -> Self {
+        self.flags.set(WindowFlags::NAV_FLATTENED, value);
+        self
+    }
     /// Builds this window, pushes it to the window stack, and starts appending to it
-    pub fn begin<'ui>(self, _: &'ui Ui<'ui>) -> ChildWindowToken<'ui> {
+    pub fn begin<'ui>(mut self, _: &'ui Ui<'ui>) -> ChildWindowToken<'ui> {
         if self.content_size[0] != 0.0 || self.content_size[1] != 0.0 {
             unsafe { sys::igSetNextWindowContentSize(self.content_size.into()) };
         }
+        if let Some((size_min, size_max)) = self.size_constraints {
+            match self.size_callback.as_mut() {
+                Some(callback) => unsafe {
+                    // SAFETY: `callback` is reinterpreted as `*mut SizeCallback<'static>` in
+                    // `size_callback_trampoline`, erasing the real `'a`. This is sound only
+                    // because Dear ImGui invokes the callback synchronously from within this
+                    // `igSetNextWindowSizeConstraints`/`igBeginChildID` pair, i.e. strictly
+                    // before `self` (and the boxed closure it owns) is dropped at the end of
+                    // `begin()`. Do not store this pointer or call the callback outside that
+                    // window.
+                    sys::igSetNextWindowSizeConstraints(
+                        size_min.into(),
+                        size_max.into(),
+                        Some(size_callback_trampoline),
+                        callback as *mut SizeCallback<'a> as *mut c_void,
+                    );
+                },
+                None => unsafe {
+                    sys::igSetNextWindowSizeConstraints(
+                        size_min.into(),
+                        size_max.into(),
+                        None,
+                        ptr::null_mut(),
+                    );
+                },
+            }
+        }
         if self.focused {
             unsafe { sys::igSetNextWindowFocus() };
         }
@@ -253,22 +369,72 @@ impl<'a> ChildWindow<'a> {
         let should_render = unsafe {
             sys::igBeginChildID(id, self.size.into(), self.border, self.flags.bits() as i32)
         };
+        if let Some(ratio) = self.scroll_x_ratio {
+            unsafe { sys::igSetScrollX(ratio * sys::igGetScrollMaxX()) };
+        }
+        if let Some(ratio) = self.scroll_y_ratio {
+            unsafe { sys::igSetScrollY(ratio * sys::igGetScrollMaxY()) };
+        }
         ChildWindowToken {
             should_render,
             should_end: true,
             _ui: PhantomData,
         }
     }
-    /// Builds this child window using the given closure to create the window content.
+    /// Builds this child window using the given closure to create the window content, returning
+    /// the closure's return value.
     ///
-    /// Note: the closure is not called if no window content is visible (e.g. window is collapsed
-    /// or fully clipped).
-    pub fn build<F: FnOnce()>(self, ui: &Ui, f: F) {
+    /// Returns `None` if no window content is visible (e.g. window is collapsed or fully
+    /// clipped), in which case the closure is not called.
+    pub fn build<R, F: FnOnce() -> R>(self, ui: &Ui, f: F) -> Option<R> {
         let window = self.begin(ui);
-        if window.should_render {
-            f();
-        }
+        let result = if window.should_render {
+            Some(f())
+        } else {
+            None
+        };
         window.end();
+        result
+    }
+}
+
+/// Data passed to a size constraint callback registered via
+/// [`size_constraints_callback`](ChildWindow::size_constraints_callback).
+pub struct SizeCallbackData<'a> {
+    raw: &'a mut sys::ImGuiSizeCallbackData,
+}
+
+impl<'a> SizeCallbackData<'a> {
+    /// Returns the position of the window for which the size is computed.
+    pub fn pos(&self) -> [f32; 2] {
+        self.raw.Pos.into()
+    }
+    /// Returns the window's current size.
+    pub fn current_size(&self) -> [f32; 2] {
+        self.raw.CurrentSize.into()
+    }
+    /// Returns the size requested by the application/user.
+    pub fn desired_size(&self) -> [f32; 2] {
+        self.raw.DesiredSize.into()
+    }
+    /// Overwrites the desired size with a constrained size.
+    pub fn set_desired_size(&mut self, size: [f32; 2]) {
+        self.raw.DesiredSize = size.into();
+    }
+}
+
+extern "C" fn size_callback_trampoline(data: *mut sys::ImGuiSizeCallbackData) {
+    unsafe {
+        let data = &mut *data;
+        // SAFETY: `UserData` points at the `SizeCallback<'a>` passed in `ChildWindow::begin`,
+        // reinterpreted here as `'static` because an `extern "C"` fn pointer can't carry a
+        // lifetime. This is only sound as long as Dear ImGui keeps calling this trampoline
+        // synchronously, from inside the `igSetNextWindowSizeConstraints`/`igBeginChildID` pair
+        // that registered it, before the real `'a` borrow could have expired.
+        let user_data = data.UserData as *mut SizeCallback<'static>;
+        let callback = &mut *user_data;
+        let mut data = SizeCallbackData { raw: &mut *data };
+        callback(&mut data);
     }
 }
 
@@ -280,7 +446,47 @@ pub struct ChildWindowToken<'ui> {
     _ui: PhantomData<&'ui Ui<'ui>>,
 }
 
+/// # Stack scoping
+///
+/// The scroll accessors below are only valid while this child window is on top of the window
+/// stack, i.e. no other `ChildWindow`/`Window` opened after this one is still awaiting `end()`.
 impl<'ui> ChildWindowToken<'ui> {
+    /// Sets the horizontal scroll position of the child window.
+    ///
+    /// See "Stack scoping" above.
+    pub fn set_scroll_x(&self, x: f32) {
+        unsafe { sys::igSetScrollX(x) };
+    }
+    /// Sets the vertical scroll position of the child window.
+    ///
+    /// See "Stack scoping" above.
+    pub fn set_scroll_y(&self, y: f32) {
+        unsafe { sys::igSetScrollY(y) };
+    }
+    /// Returns the current horizontal scroll position of the child window.
+    ///
+    /// See "Stack scoping" above.
+    pub fn scroll_x(&self) -> f32 {
+        unsafe { sys::igGetScrollX() }
+    }
+    /// Returns the current vertical scroll position of the child window.
+    ///
+    /// See "Stack scoping" above.
+    pub fn scroll_y(&self) -> f32 {
+        unsafe { sys::igGetScrollY() }
+    }
+    /// Returns the maximum horizontal scroll position of the child window.
+    ///
+    /// See "Stack scoping" above.
+    pub fn scroll_max_x(&self) -> f32 {
+        unsafe { sys::igGetScrollMaxX() }
+    }
+    /// Returns the maximum vertical scroll position of the child window.
+    ///
+    /// See "Stack scoping" above.
+    pub fn scroll_max_y(&self) -> f32 {
+        unsafe { sys::igGetScrollMaxY() }
+    }
     /// Finishes the current child window and pops it from the window stack
     pub fn end(mut self) {
         self.should_end = false;
@@ -294,4 +500,4 @@ impl<'ui> Drop for ChildWindowToken<'ui> {
             unsafe { sys::igEndChild() };
         }
     }
-}
\ No newline at end of file
+}